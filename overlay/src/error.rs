@@ -0,0 +1,30 @@
+use imgui_winit_support::winit::error::OsError;
+
+pub type Result<T> = std::result::Result<T, OverlayError>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum OverlayError {
+    #[error("window creation failed: {0}")]
+    WindowCreation(#[from] OsError),
+
+    #[error("windows API call failed: {0}")]
+    WindowsApi(#[from] windows::core::Error),
+
+    #[error("desktop window manager composition is disabled")]
+    DwmCompositionDisabled,
+
+    #[error("no suitable GPU or driver found for any render backend")]
+    NoRenderBackendAvailable,
+
+    #[error("failed to acquire a device context for the overlay window")]
+    OpenGLDeviceContextUnavailable,
+
+    #[error("failed to create or activate the WGL/OpenGL context")]
+    OpenGLContextCreationFailed,
+
+    #[error("failed to initialize the Vulkan render backend")]
+    VulkanInitFailed,
+
+    #[error("could not find the target window")]
+    TargetWindowNotFound,
+}
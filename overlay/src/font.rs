@@ -0,0 +1,163 @@
+use std::{
+    cell::RefCell,
+    collections::BTreeSet,
+    ops::Range,
+};
+
+use imgui::{
+    FontConfig,
+    FontGlyphRanges,
+    FontSource,
+};
+
+use crate::Result;
+
+// codepoints come in via register_codepoints up front or queue_codepoints
+// on demand (see UnicodeTextRenderer); only codepoints up to U+FFFF are
+// supported, since ImGui's glyph ranges are 16-bit
+pub struct FontAtlasBuilder {
+    fonts: Vec<Vec<u8>>,
+    codepoints: BTreeSet<u32>,
+    pending_codepoints: BTreeSet<u32>,
+    dirty: bool,
+}
+
+impl FontAtlasBuilder {
+    pub fn new() -> Self {
+        Self {
+            fonts: Vec::new(),
+            codepoints: BTreeSet::new(),
+            pending_codepoints: BTreeSet::new(),
+            dirty: false,
+        }
+    }
+
+    pub fn register_font(&mut self, data: &[u8]) -> Result<()> {
+        self.fonts.push(data.to_vec());
+        self.dirty = true;
+        Ok(())
+    }
+
+    pub fn register_codepoints(&mut self, codepoints: Range<u32>) {
+        self.codepoints.extend(codepoints);
+        self.dirty = true;
+    }
+
+    // skips already-registered/queued codepoints, and anything above U+FFFF
+    pub fn queue_codepoints(&mut self, codepoints: impl Iterator<Item = char>) {
+        for codepoint in codepoints {
+            let codepoint = codepoint as u32;
+            if codepoint > 0xFFFF || self.codepoints.contains(&codepoint) {
+                continue;
+            }
+
+            self.pending_codepoints.insert(codepoint);
+        }
+    }
+
+    pub fn fetch_reset_flag_updated(&mut self) -> bool {
+        if !self.pending_codepoints.is_empty() {
+            self.codepoints.append(&mut self.pending_codepoints);
+            self.dirty = true;
+        }
+
+        std::mem::take(&mut self.dirty)
+    }
+
+    pub fn build_font_source(&self, size: f32) -> (Vec<FontSource<'_>>, Vec<u16>) {
+        // range list is u16 pairs terminated by 0, so a truncated U+10000
+        // etc. would end it early - already filtered out in queue_codepoints,
+        // this is just a backstop for codepoints added via register_codepoints.
+        let mut glyph_ranges = Vec::with_capacity(self.codepoints.len() * 2 + 1);
+        for &codepoint in self.codepoints.range(..=0xFFFF) {
+            glyph_ranges.push(codepoint as u16);
+            glyph_ranges.push(codepoint as u16);
+        }
+        glyph_ranges.push(0);
+
+        let font_sources = self
+            .fonts
+            .iter()
+            .map(|data| FontSource::TtfData {
+                data,
+                size_pixels: size,
+                config: Some(FontConfig {
+                    glyph_ranges: FontGlyphRanges::from_slice(&glyph_ranges),
+                    ..FontConfig::default()
+                }),
+            })
+            .collect();
+
+        (font_sources, glyph_ranges)
+    }
+}
+
+// draws text through imgui, queuing any codepoints not yet in the atlas
+pub struct UnicodeTextRenderer<'a> {
+    ui: &'a imgui::Ui,
+    fonts: RefCell<&'a mut FontAtlasBuilder>,
+}
+
+impl<'a> UnicodeTextRenderer<'a> {
+    pub fn new(ui: &'a imgui::Ui, fonts: &'a mut FontAtlasBuilder) -> Self {
+        Self {
+            ui,
+            fonts: RefCell::new(fonts),
+        }
+    }
+
+    pub fn text(&self, text: impl AsRef<str>) {
+        let text = text.as_ref();
+        self.queue_missing_codepoints(text);
+        self.ui.text(text);
+    }
+
+    pub fn text_colored(&self, color: [f32; 4], text: impl AsRef<str>) {
+        let text = text.as_ref();
+        self.queue_missing_codepoints(text);
+        self.ui.text_colored(color, text);
+    }
+
+    fn queue_missing_codepoints(&self, text: &str) {
+        self.fonts.borrow_mut().queue_codepoints(text.chars());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_font_source_drops_codepoints_above_bmp() {
+        let mut fonts = FontAtlasBuilder::new();
+        fonts.register_codepoints(0xFFFE..0xFFFF);
+        fonts.register_codepoints(0x10000..0x10001);
+        fonts.register_codepoints(0x20000..0x20001);
+
+        let (_, glyph_ranges) = fonts.build_font_source(13.0);
+
+        // an astral codepoint truncated to u16 would be 0, the range-list
+        // terminator, and would have left extra 0 entries after 0xFFFE
+        assert_eq!(glyph_ranges, vec![0xFFFE, 0xFFFE, 0]);
+    }
+
+    #[test]
+    fn queue_codepoints_drops_codepoints_above_bmp() {
+        let mut fonts = FontAtlasBuilder::new();
+        fonts.queue_codepoints(['\u{FFFE}', '\u{10000}'].into_iter());
+        fonts.fetch_reset_flag_updated();
+
+        let (_, glyph_ranges) = fonts.build_font_source(13.0);
+        assert_eq!(glyph_ranges, vec![0xFFFE, 0xFFFE, 0]);
+    }
+
+    #[test]
+    fn fetch_reset_flag_updated_folds_pending_and_resets_dirty() {
+        let mut fonts = FontAtlasBuilder::new();
+        assert!(!fonts.fetch_reset_flag_updated());
+
+        fonts.queue_codepoints(['a'].into_iter());
+        assert!(fonts.fetch_reset_flag_updated());
+        assert!(!fonts.fetch_reset_flag_updated());
+    }
+}
@@ -1,5 +1,8 @@
 #![feature(str_from_utf16_endian)]
-use std::time::Instant;
+use std::time::{
+    Duration,
+    Instant,
+};
 
 use clipboard::ClipboardSupport;
 use copypasta::ClipboardContext;
@@ -59,6 +62,7 @@ use windows::Win32::{
         },
     },
     UI::WindowsAndMessaging::{
+        GetWindowLongPtrA,
         SetWindowDisplayAffinity,
         SetWindowLongA,
         SetWindowLongPtrA,
@@ -92,8 +96,16 @@ pub use window_tracker::OverlayTarget;
 
 mod vulkan;
 
+mod opengl;
+use opengl::OpenGLRenderBackend;
+
 mod perf;
-pub use perf::PerfTracker;
+pub use perf::{
+    FrameRecord,
+    PerfTracker,
+    StageMark,
+    StageStats,
+};
 
 mod font;
 mod util;
@@ -164,10 +176,65 @@ fn create_imgui_context(_options: &OverlayOptions) -> Result<(WinitPlatform, img
     Ok((platform, imgui))
 }
 
+// `Auto` tries Vulkan then OpenGL, falling back on the next one if a
+// backend fails to initialize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenderBackendKind {
+    Auto,
+    Vulkan,
+    OpenGL,
+}
+
+impl Default for RenderBackendKind {
+    fn default() -> Self {
+        Self::Auto
+    }
+}
+
+fn create_render_backend(
+    preferred: RenderBackendKind,
+    window: &Window,
+    imgui: &mut imgui::Context,
+) -> Result<Box<dyn RenderBackend>> {
+    let attempts: &[RenderBackendKind] = match preferred {
+        RenderBackendKind::Auto => &[RenderBackendKind::Vulkan, RenderBackendKind::OpenGL],
+        RenderBackendKind::Vulkan => &[RenderBackendKind::Vulkan],
+        RenderBackendKind::OpenGL => &[RenderBackendKind::OpenGL],
+    };
+
+    let mut last_error = None;
+    for (index, kind) in attempts.iter().enumerate() {
+        let result: Result<Box<dyn RenderBackend>> = match kind {
+            RenderBackendKind::Vulkan => {
+                VulkanRenderBackend::new(window, imgui).map(|backend| Box::new(backend) as _)
+            }
+            RenderBackendKind::OpenGL => {
+                OpenGLRenderBackend::new(window, imgui).map(|backend| Box::new(backend) as _)
+            }
+            RenderBackendKind::Auto => unreachable!("Auto is expanded before attempting backends"),
+        };
+
+        match result {
+            Ok(backend) => return Ok(backend),
+            Err(error) => {
+                log::warn!("Failed to initialize {:?} render backend: {}", kind, error);
+                last_error = Some(error);
+                if index + 1 < attempts.len() {
+                    log::info!("Falling back to {:?} render backend", attempts[index + 1]);
+                }
+            }
+        }
+    }
+
+    Err(last_error.expect("at least one render backend is always attempted"))
+}
+
 pub struct OverlayOptions {
     pub title: String,
     pub target: OverlayTarget,
     pub register_fonts_callback: Option<Box<dyn Fn(&mut FontAtlas) -> ()>>,
+    pub preferred_backend: RenderBackendKind,
+    pub frame_pacing: FramePacing,
 }
 
 pub trait RenderBackend {
@@ -178,6 +245,27 @@ pub trait RenderBackend {
         window: &Window,
         draw_data: &imgui::DrawData,
     );
+
+    /// Requests (or stops requesting) a vsync'd present mode, e.g. Vulkan
+    /// `PRESENT_MODE_FIFO_KHR`. Backends that can't choose one can ignore this.
+    fn set_vsync(&mut self, _enabled: bool) {}
+}
+
+// Controls how aggressively the event loop redraws the overlay.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FramePacing {
+    // paced by the backend's vsync'd present mode; the event loop still polls
+    Vsync,
+    // no pacing; redraw as fast as the event loop and backend allow
+    Uncapped,
+    // fixed target frame rate via ControlFlow::WaitUntil
+    Fixed(u32),
+}
+
+impl Default for FramePacing {
+    fn default() -> Self {
+        Self::Vsync
+    }
 }
 
 pub struct System {
@@ -191,6 +279,7 @@ pub struct System {
     pub imgui_register_fonts_callback: Option<Box<dyn Fn(&mut FontAtlas) -> ()>>,
 
     pub window_tracker: WindowTracker,
+    pub frame_pacing: FramePacing,
 
     renderer: Box<dyn RenderBackend>,
 }
@@ -202,7 +291,7 @@ pub fn init(options: OverlayOptions) -> Result<System> {
     let window = create_window(&event_loop, &options.title)?;
 
     let (mut platform, mut imgui) = create_imgui_context(&options)?;
-    platform.attach_window(imgui.io_mut(), &window, HiDpiMode::Default);
+    platform.attach_window(imgui.io_mut(), &window, HiDpiMode::Rounded);
 
     let mut imgui_fonts = FontAtlasBuilder::new();
     imgui_fonts.register_font(include_bytes!("../resources/Roboto-Regular.ttf"))?;
@@ -211,7 +300,9 @@ pub fn init(options: OverlayOptions) -> Result<System> {
     imgui_fonts.register_font(include_bytes!("../resources/unifont-15.1.05.otf"))?;
     imgui_fonts.register_codepoints(1..255);
 
-    let renderer = Box::new(VulkanRenderBackend::new(&window, &mut imgui)?);
+    let mut renderer = create_render_backend(options.preferred_backend, &window, &mut imgui)?;
+    renderer.set_vsync(matches!(options.frame_pacing, FramePacing::Vsync));
+
     Ok(System {
         event_loop,
         window,
@@ -222,6 +313,7 @@ pub fn init(options: OverlayOptions) -> Result<System> {
 
         platform,
         window_tracker,
+        frame_pacing: options.frame_pacing,
 
         renderer,
     })
@@ -229,6 +321,10 @@ pub fn init(options: OverlayOptions) -> Result<System> {
 
 const PERF_RECORDS: usize = 2048;
 
+/// Base font size in logical pixels, scaled by the target window's monitor
+/// DPI before being handed to `FontAtlasBuilder`.
+const BASE_FONT_SIZE: f32 = 18.0;
+
 impl System {
     pub fn main_loop<U, R>(self, mut update: U, mut render: R) -> i32
     where
@@ -245,6 +341,7 @@ impl System {
 
             mut platform,
             window_tracker,
+            frame_pacing,
 
             mut renderer,
             ..
@@ -262,14 +359,22 @@ impl System {
             key_input_system: KeyboardInputSystem::new(),
             mouse_input_system: MouseInputSystem::new(),
             window_tracker,
+            frame_pacing,
 
             frame_count: 0,
             debug_overlay_shown: false,
         };
 
+        let mut font_scale_factor = runtime_controller.window_tracker.target_scale_factor();
+        let mut font_rebuild_pending = true;
+
+        let mut renderer_vsync = matches!(frame_pacing, FramePacing::Vsync);
+
         let mut perf = PerfTracker::new(PERF_RECORDS);
         let result = event_loop.run_return(move |event, _, control_flow| {
-            *control_flow = ControlFlow::Poll;
+            /* control_flow is only overwritten by the arms below; leaving it
+            alone on every other event (RedrawEventsCleared, device events, ...)
+            is what makes FramePacing::Fixed's WaitUntil actually stick */
             platform.handle_event(runtime_controller.imgui.io_mut(), &window, &event);
 
             match event {
@@ -300,12 +405,27 @@ impl System {
                             return;
                         }
 
+                        // re-checked every frame so a target that moved to a
+                        // different-DPI monitor is picked up immediately
+                        let target_scale_factor = runtime_controller.window_tracker.target_scale_factor();
+                        if target_scale_factor != font_scale_factor {
+                            font_scale_factor = target_scale_factor;
+                            font_rebuild_pending = true;
+                        }
+
                         if runtime_controller.imgui_fonts.fetch_reset_flag_updated() {
+                            font_rebuild_pending = true;
+                        }
+
+                        if font_rebuild_pending {
+                            font_rebuild_pending = false;
+
                             let font_atlas = runtime_controller.imgui.fonts();
                             font_atlas.clear();
 
-                            let (font_sources, _glyph_memory) =
-                                runtime_controller.imgui_fonts.build_font_source(18.0);
+                            let (font_sources, _glyph_memory) = runtime_controller
+                                .imgui_fonts
+                                .build_font_source(BASE_FONT_SIZE * font_scale_factor as f32);
 
                             font_atlas.add_font(&font_sources);
                             if let Some(user_callback) = &imgui_register_fonts_callback {
@@ -371,9 +491,22 @@ impl System {
                     };
 
                     /* render */
+                    let frame_pacing = runtime_controller.frame_pacing();
+                    let wants_vsync = matches!(frame_pacing, FramePacing::Vsync);
+                    if wants_vsync != renderer_vsync {
+                        renderer_vsync = wants_vsync;
+                        renderer.set_vsync(renderer_vsync);
+                    }
                     renderer.render_frame(&mut perf, &window, draw_data);
 
                     runtime_controller.frame_rendered();
+
+                    *control_flow = match frame_pacing {
+                        FramePacing::Vsync | FramePacing::Uncapped => ControlFlow::Poll,
+                        FramePacing::Fixed(fps) => {
+                            ControlFlow::WaitUntil(last_frame + Duration::from_secs_f64(1.0 / fps.max(1) as f64))
+                        }
+                    };
                 }
                 Event::WindowEvent {
                     event: WindowEvent::CloseRequested,
@@ -400,6 +533,7 @@ pub struct SystemRuntimeController {
     key_input_system: KeyboardInputSystem,
 
     window_tracker: WindowTracker,
+    frame_pacing: FramePacing,
 
     frame_count: u64,
 }
@@ -445,6 +579,46 @@ impl SystemRuntimeController {
         }
     }
 
+    // Toggles click-through (WS_EX_TRANSPARENT) on the overlay window.
+    pub fn set_input_passthrough(&self, enabled: bool) {
+        unsafe {
+            let current_ex_style = GetWindowLongPtrA(self.hwnd, GWL_EXSTYLE);
+            let new_ex_style = if enabled {
+                current_ex_style | WS_EX_TRANSPARENT.0 as isize
+            } else {
+                current_ex_style & !(WS_EX_TRANSPARENT.0 as isize)
+            };
+
+            SetWindowLongPtrA(self.hwnd, GWL_EXSTYLE, new_ex_style);
+        }
+    }
+
+    // Enables/disables the DWM blur-behind region on the overlay window.
+    pub fn set_blur_behind(&self, enabled: bool) {
+        unsafe {
+            let mut bb: DWM_BLURBEHIND = Default::default();
+            bb.dwFlags = DWM_BB_ENABLE | DWM_BB_BLURREGION;
+            bb.fEnable = BOOL::from(enabled);
+            bb.hRgnBlur = if enabled { CreateRectRgn(0, 0, 1, 1) } else { Default::default() };
+
+            if let Err(error) = DwmEnableBlurBehindWindow(self.hwnd, &bb) {
+                log::warn!("Failed to update DWM blur-behind state: {}", error);
+            }
+
+            if enabled {
+                DeleteObject(bb.hRgnBlur);
+            }
+        }
+    }
+
+    pub fn set_frame_pacing(&mut self, pacing: FramePacing) {
+        self.frame_pacing = pacing;
+    }
+
+    pub fn frame_pacing(&self) -> FramePacing {
+        self.frame_pacing
+    }
+
     pub fn toggle_debug_overlay(&mut self, visible: bool) {
         self.debug_overlay_shown = visible;
     }
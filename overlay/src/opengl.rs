@@ -0,0 +1,103 @@
+use imgui_winit_support::winit::{
+    platform::windows::WindowExtWindows,
+    window::Window,
+};
+use windows::Win32::{
+    Foundation::HWND,
+    Graphics::{
+        Gdi::{
+            GetDC,
+            ReleaseDC,
+            HDC,
+        },
+        OpenGL::{
+            wglCreateContext,
+            wglDeleteContext,
+            wglGetProcAddress,
+            wglMakeCurrent,
+            SwapBuffers,
+            HGLRC,
+        },
+    },
+};
+
+use crate::{
+    OverlayError,
+    PerfTracker,
+    RenderBackend,
+    Result,
+};
+
+/// `RenderBackend` on top of a plain WGL/OpenGL context; the fallback when
+/// Vulkan isn't available.
+pub struct OpenGLRenderBackend {
+    hwnd: HWND,
+    device_context: HDC,
+    gl_context: HGLRC,
+    renderer: imgui_opengl_renderer::Renderer,
+}
+
+impl OpenGLRenderBackend {
+    pub fn new(window: &Window, imgui: &mut imgui::Context) -> Result<Self> {
+        let hwnd = HWND(window.hwnd() as isize);
+        let device_context = unsafe { GetDC(hwnd) };
+        if device_context.is_invalid() {
+            return Err(OverlayError::OpenGLDeviceContextUnavailable);
+        }
+
+        let gl_context = match unsafe { wglCreateContext(device_context) } {
+            Ok(gl_context) => gl_context,
+            Err(_) => {
+                unsafe { ReleaseDC(hwnd, device_context) };
+                return Err(OverlayError::OpenGLContextCreationFailed);
+            }
+        };
+
+        if unsafe { wglMakeCurrent(device_context, gl_context) }.is_err() {
+            unsafe {
+                let _ = wglDeleteContext(gl_context);
+                ReleaseDC(hwnd, device_context);
+            }
+            return Err(OverlayError::OpenGLContextCreationFailed);
+        }
+
+        let renderer = imgui_opengl_renderer::Renderer::new(imgui, |symbol| unsafe {
+            wglGetProcAddress(windows::core::PCSTR(
+                std::ffi::CString::new(symbol).unwrap_or_default().as_ptr() as _,
+            ))
+            .map(|proc| proc as _)
+            .unwrap_or(std::ptr::null())
+        });
+
+        Ok(Self {
+            hwnd,
+            device_context,
+            gl_context,
+            renderer,
+        })
+    }
+}
+
+impl RenderBackend for OpenGLRenderBackend {
+    fn update_fonts_texture(&mut self, imgui: &mut imgui::Context) {
+        self.renderer.reload_font_texture(imgui);
+    }
+
+    fn render_frame(&mut self, perf: &mut PerfTracker, _window: &Window, draw_data: &imgui::DrawData) {
+        self.renderer.render(draw_data);
+
+        unsafe {
+            SwapBuffers(self.device_context);
+        }
+        perf.mark("gl swap buffers");
+    }
+}
+
+impl Drop for OpenGLRenderBackend {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = wglDeleteContext(self.gl_context);
+            ReleaseDC(self.hwnd, self.device_context);
+        }
+    }
+}
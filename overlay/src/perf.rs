@@ -0,0 +1,183 @@
+use std::{
+    collections::VecDeque,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// A single named stage duration within one frame, e.g. `("render frame", 1.2ms)`.
+#[derive(Debug, Clone)]
+pub struct StageMark {
+    pub name: &'static str,
+    pub duration: Duration,
+}
+
+/// All stage marks recorded between one `begin()` and the next.
+#[derive(Debug, Clone, Default)]
+pub struct FrameRecord {
+    pub marks: Vec<StageMark>,
+}
+
+impl FrameRecord {
+    pub fn total(&self) -> Duration {
+        self.marks.iter().map(|mark| mark.duration).sum()
+    }
+
+    pub fn stage(&self, name: &str) -> Option<Duration> {
+        self.marks
+            .iter()
+            .find(|mark| mark.name == name)
+            .map(|mark| mark.duration)
+    }
+}
+
+/// Aggregate timing stats for a single named stage across the recorded frame history.
+#[derive(Debug, Clone, Copy)]
+pub struct StageStats {
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub p95: Duration,
+    pub sample_count: usize,
+}
+
+type FrameCallback = Box<dyn FnMut(&FrameRecord) + Send>;
+
+/// Records per-stage frame timings (via `begin`/`mark`) and keeps a bounded
+/// history of completed frames, for both the on-screen debug plot
+/// (`render`) and programmatic consumption (`recent_frames`, `stage_stats`,
+/// `set_frame_callback`).
+pub struct PerfTracker {
+    history_length: usize,
+    history: VecDeque<FrameRecord>,
+
+    frame_start: Instant,
+    last_mark: Instant,
+    current: FrameRecord,
+
+    on_frame: Option<FrameCallback>,
+}
+
+impl PerfTracker {
+    pub fn new(history_length: usize) -> Self {
+        Self {
+            history_length,
+            history: VecDeque::with_capacity(history_length),
+
+            frame_start: Instant::now(),
+            last_mark: Instant::now(),
+            current: FrameRecord::default(),
+
+            on_frame: None,
+        }
+    }
+
+    /// Starts timing a new frame, finalizing the previous one into history.
+    pub fn begin(&mut self) {
+        if !self.current.marks.is_empty() {
+            let record = std::mem::take(&mut self.current);
+            if let Some(callback) = &mut self.on_frame {
+                callback(&record);
+            }
+
+            if self.history.len() >= self.history_length {
+                self.history.pop_front();
+            }
+            self.history.push_back(record);
+        }
+
+        self.frame_start = Instant::now();
+        self.last_mark = self.frame_start;
+    }
+
+    /// Records the duration since the previous mark (or `begin`) under `name`.
+    pub fn mark(&mut self, name: &'static str) {
+        let now = Instant::now();
+        self.current.marks.push(StageMark {
+            name,
+            duration: now - self.last_mark,
+        });
+        self.last_mark = now;
+    }
+
+    pub fn history_length(&self) -> usize {
+        self.history_length
+    }
+
+    pub fn set_history_length(&mut self, history_length: usize) {
+        self.history_length = history_length;
+        while self.history.len() > history_length {
+            self.history.pop_front();
+        }
+    }
+
+    /// Invoked with each frame's `FrameRecord` as soon as it's finalized.
+    pub fn set_frame_callback(&mut self, callback: impl FnMut(&FrameRecord) + Send + 'static) {
+        self.on_frame = Some(Box::new(callback));
+    }
+
+    /// Returns up to the last `count` completed frames, oldest first.
+    pub fn recent_frames(&self, count: usize) -> Vec<FrameRecord> {
+        let skip = self.history.len().saturating_sub(count);
+        self.history.iter().skip(skip).cloned().collect()
+    }
+
+    /// Aggregate min/max/mean/p95 stats per stage name across the history.
+    pub fn stage_stats(&self) -> Vec<(&'static str, StageStats)> {
+        let mut durations_by_stage: Vec<(&'static str, Vec<Duration>)> = Vec::new();
+
+        for record in &self.history {
+            for mark in &record.marks {
+                match durations_by_stage
+                    .iter_mut()
+                    .find(|(name, _)| *name == mark.name)
+                {
+                    Some((_, durations)) => durations.push(mark.duration),
+                    None => durations_by_stage.push((mark.name, vec![mark.duration])),
+                }
+            }
+        }
+
+        durations_by_stage
+            .into_iter()
+            .map(|(name, mut durations)| {
+                durations.sort();
+
+                let sample_count = durations.len();
+                let sum: Duration = durations.iter().sum();
+                let p95_index = ((sample_count as f64 * 0.95) as usize).min(sample_count - 1);
+
+                (
+                    name,
+                    StageStats {
+                        min: durations[0],
+                        max: durations[sample_count - 1],
+                        mean: sum / sample_count as u32,
+                        p95: durations[p95_index],
+                        sample_count,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Renders the total time of each recorded frame as a plot into the debug
+    /// overlay window; individual stages are not plotted.
+    pub fn render(&self, ui: &imgui::Ui, size: [f32; 2]) {
+        if self.history.is_empty() {
+            ui.text("No frame history recorded yet.");
+            return;
+        }
+
+        let frame_times = self
+            .history
+            .iter()
+            .map(|record| record.total().as_secs_f32() * 1000.0)
+            .collect::<Vec<_>>();
+
+        ui.plot_lines("Frame Time (ms)", &frame_times)
+            .graph_size(size)
+            .build();
+    }
+}
@@ -0,0 +1,261 @@
+use ash::vk;
+use imgui_winit_support::winit::{
+    platform::windows::WindowExtWindows,
+    window::Window,
+};
+
+use crate::{
+    OverlayError,
+    PerfTracker,
+    RenderBackend,
+    Result,
+};
+
+pub struct VulkanRenderBackend {
+    _entry: ash::Entry,
+    instance: ash::Instance,
+    physical_device: vk::PhysicalDevice,
+    device: ash::Device,
+    surface_loader: ash::extensions::khr::Surface,
+    surface: vk::SurfaceKHR,
+    swapchain_loader: ash::extensions::khr::Swapchain,
+    swapchain: vk::SwapchainKHR,
+    present_mode: vk::PresentModeKHR,
+    present_mode_dirty: bool,
+
+    renderer: imgui_rs_vulkan_renderer::Renderer,
+}
+
+impl VulkanRenderBackend {
+    pub fn new(window: &Window, imgui: &mut imgui::Context) -> Result<Self> {
+        let entry = unsafe { ash::Entry::load() }.map_err(|_| OverlayError::VulkanInitFailed)?;
+        let instance = create_instance(&entry)?;
+
+        let surface_loader = ash::extensions::khr::Surface::new(&entry, &instance);
+        let surface = create_win32_surface(&entry, &instance, window)?;
+
+        let physical_device = pick_physical_device(&instance, &surface_loader, surface)?;
+        let (device, graphics_queue_family) = create_device(&instance, physical_device)?;
+
+        let swapchain_loader = ash::extensions::khr::Swapchain::new(&instance, &device);
+        let present_mode = vk::PresentModeKHR::FIFO;
+        let swapchain = create_swapchain(
+            &surface_loader,
+            &swapchain_loader,
+            physical_device,
+            surface,
+            window,
+            present_mode,
+        )?;
+
+        let renderer = imgui_rs_vulkan_renderer::Renderer::with_default_allocator(
+            &instance,
+            physical_device,
+            device.clone(),
+            device.get_device_queue(graphics_queue_family, 0),
+            imgui_rs_vulkan_renderer::DynamicRendering {
+                color_attachment_format: vk::Format::B8G8R8A8_UNORM,
+                depth_attachment_format: None,
+            },
+            imgui,
+            None,
+        )
+        .map_err(|_| OverlayError::VulkanInitFailed)?;
+
+        Ok(Self {
+            _entry: entry,
+            instance,
+            physical_device,
+            device,
+            surface_loader,
+            surface,
+            swapchain_loader,
+            swapchain,
+            present_mode,
+            present_mode_dirty: false,
+            renderer,
+        })
+    }
+
+    // deferred to the next render_frame so set_vsync never stalls mid-frame
+    fn apply_pending_present_mode(&mut self, window: &Window) {
+        if !self.present_mode_dirty {
+            return;
+        }
+        self.present_mode_dirty = false;
+
+        match create_swapchain(
+            &self.surface_loader,
+            &self.swapchain_loader,
+            self.physical_device,
+            self.surface,
+            window,
+            self.present_mode,
+        ) {
+            Ok(swapchain) => {
+                unsafe {
+                    self.swapchain_loader.destroy_swapchain(self.swapchain, None);
+                }
+                self.swapchain = swapchain;
+            }
+            Err(error) => {
+                log::warn!("Failed to apply present mode {:?}: {}", self.present_mode, error);
+            }
+        }
+    }
+}
+
+impl RenderBackend for VulkanRenderBackend {
+    fn update_fonts_texture(&mut self, imgui: &mut imgui::Context) {
+        if let Err(error) = self.renderer.update_fonts_texture(imgui.fonts()) {
+            log::warn!("Failed to update Vulkan font atlas texture: {}", error);
+        }
+    }
+
+    fn render_frame(&mut self, perf: &mut PerfTracker, window: &Window, draw_data: &imgui::DrawData) {
+        self.apply_pending_present_mode(window);
+
+        if let Err(error) = self.renderer.cmd_draw(draw_data) {
+            log::warn!("Vulkan frame render failed: {}", error);
+        }
+        perf.mark("vulkan present");
+    }
+
+    fn set_vsync(&mut self, enabled: bool) {
+        let present_mode = if enabled {
+            vk::PresentModeKHR::FIFO
+        } else {
+            vk::PresentModeKHR::IMMEDIATE
+        };
+
+        if present_mode != self.present_mode {
+            self.present_mode = present_mode;
+            self.present_mode_dirty = true;
+        }
+    }
+}
+
+impl Drop for VulkanRenderBackend {
+    fn drop(&mut self) {
+        unsafe {
+            self.swapchain_loader.destroy_swapchain(self.swapchain, None);
+            self.surface_loader.destroy_surface(self.surface, None);
+            self.device.destroy_device(None);
+            self.instance.destroy_instance(None);
+        }
+    }
+}
+
+fn create_instance(entry: &ash::Entry) -> Result<ash::Instance> {
+    let app_info = vk::ApplicationInfo::builder().api_version(vk::API_VERSION_1_2);
+    let extensions = [
+        ash::extensions::khr::Surface::name().as_ptr(),
+        ash::extensions::khr::Win32Surface::name().as_ptr(),
+    ];
+    let create_info = vk::InstanceCreateInfo::builder()
+        .application_info(&app_info)
+        .enabled_extension_names(&extensions);
+
+    unsafe { entry.create_instance(&create_info, None) }.map_err(|_| OverlayError::VulkanInitFailed)
+}
+
+fn create_win32_surface(
+    entry: &ash::Entry,
+    instance: &ash::Instance,
+    window: &Window,
+) -> Result<vk::SurfaceKHR> {
+    let win32_surface_loader = ash::extensions::khr::Win32Surface::new(entry, instance);
+    let create_info = vk::Win32SurfaceCreateInfoKHR::builder()
+        .hwnd(window.hwnd())
+        .hinstance(window.hinstance());
+
+    unsafe { win32_surface_loader.create_win32_surface(&create_info, None) }
+        .map_err(|_| OverlayError::VulkanInitFailed)
+}
+
+fn pick_physical_device(
+    instance: &ash::Instance,
+    surface_loader: &ash::extensions::khr::Surface,
+    surface: vk::SurfaceKHR,
+) -> Result<vk::PhysicalDevice> {
+    let devices =
+        unsafe { instance.enumerate_physical_devices() }.map_err(|_| OverlayError::VulkanInitFailed)?;
+
+    devices
+        .into_iter()
+        .find(|&device| unsafe {
+            surface_loader
+                .get_physical_device_surface_support(device, 0, surface)
+                .unwrap_or(false)
+        })
+        .ok_or(OverlayError::VulkanInitFailed)
+}
+
+fn create_device(
+    instance: &ash::Instance,
+    physical_device: vk::PhysicalDevice,
+) -> Result<(ash::Device, u32)> {
+    let graphics_queue_family = unsafe {
+        instance.get_physical_device_queue_family_properties(physical_device)
+    }
+    .into_iter()
+    .position(|family| family.queue_flags.contains(vk::QueueFlags::GRAPHICS))
+    .ok_or(OverlayError::VulkanInitFailed)? as u32;
+
+    let queue_priorities = [1.0f32];
+    let queue_create_info = vk::DeviceQueueCreateInfo::builder()
+        .queue_family_index(graphics_queue_family)
+        .queue_priorities(&queue_priorities);
+    let queue_create_infos = [*queue_create_info];
+    let extensions = [ash::extensions::khr::Swapchain::name().as_ptr()];
+    let create_info = vk::DeviceCreateInfo::builder()
+        .queue_create_infos(&queue_create_infos)
+        .enabled_extension_names(&extensions);
+
+    let device = unsafe { instance.create_device(physical_device, &create_info, None) }
+        .map_err(|_| OverlayError::VulkanInitFailed)?;
+
+    Ok((device, graphics_queue_family))
+}
+
+fn create_swapchain(
+    surface_loader: &ash::extensions::khr::Surface,
+    swapchain_loader: &ash::extensions::khr::Swapchain,
+    physical_device: vk::PhysicalDevice,
+    surface: vk::SurfaceKHR,
+    window: &Window,
+    present_mode: vk::PresentModeKHR,
+) -> Result<vk::SwapchainKHR> {
+    let capabilities =
+        unsafe { surface_loader.get_physical_device_surface_capabilities(physical_device, surface) }
+            .map_err(|_| OverlayError::VulkanInitFailed)?;
+
+    let supported_present_modes =
+        unsafe { surface_loader.get_physical_device_surface_present_modes(physical_device, surface) }
+            .map_err(|_| OverlayError::VulkanInitFailed)?;
+    let present_mode = if supported_present_modes.contains(&present_mode) {
+        present_mode
+    } else {
+        vk::PresentModeKHR::FIFO
+    };
+
+    let size = window.inner_size();
+    let create_info = vk::SwapchainCreateInfoKHR::builder()
+        .surface(surface)
+        .min_image_count(capabilities.min_image_count.max(2))
+        .image_format(vk::Format::B8G8R8A8_UNORM)
+        .image_color_space(vk::ColorSpaceKHR::SRGB_NONLINEAR)
+        .image_extent(vk::Extent2D {
+            width: size.width,
+            height: size.height,
+        })
+        .image_array_layers(1)
+        .image_usage(vk::ImageUsageFlags::COLOR_ATTACHMENT)
+        .pre_transform(capabilities.current_transform)
+        .composite_alpha(vk::CompositeAlphaFlagsKHR::OPAQUE)
+        .present_mode(present_mode)
+        .clipped(true);
+
+    unsafe { swapchain_loader.create_swapchain(&create_info, None) }
+        .map_err(|_| OverlayError::VulkanInitFailed)
+}
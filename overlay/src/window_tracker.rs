@@ -0,0 +1,131 @@
+use imgui_winit_support::winit::{
+    platform::windows::WindowExtWindows,
+    window::Window,
+};
+use windows::{
+    core::PCSTR,
+    Win32::{
+        Foundation::{
+            HWND,
+            RECT,
+        },
+        UI::{
+            HiDpi::GetDpiForWindow,
+            WindowsAndMessaging::{
+                FindWindowA,
+                GetWindowRect,
+                IsWindow,
+                SetWindowPos,
+                SWP_NOACTIVATE,
+                SWP_NOZORDER,
+            },
+        },
+    },
+};
+
+use crate::{
+    OverlayError,
+    Result,
+};
+
+pub enum OverlayTarget {
+    WindowOfTitle(String),
+    Window(HWND),
+}
+
+pub struct WindowTracker {
+    target: HWND,
+    last_rect: RECT,
+    force_update: bool,
+}
+
+impl WindowTracker {
+    pub fn new(target: &OverlayTarget) -> Result<Self> {
+        let target_hwnd = match target {
+            OverlayTarget::Window(hwnd) => *hwnd,
+            OverlayTarget::WindowOfTitle(title) => {
+                let title = std::ffi::CString::new(title.as_str()).unwrap_or_default();
+                let hwnd = unsafe { FindWindowA(PCSTR::null(), PCSTR(title.as_ptr() as _)) };
+                if hwnd.0 == 0 {
+                    return Err(OverlayError::TargetWindowNotFound);
+                }
+                hwnd
+            }
+        };
+
+        Ok(Self {
+            target: target_hwnd,
+            last_rect: RECT::default(),
+            force_update: true,
+        })
+    }
+
+    // repositions/resizes the overlay to match the target; returns false
+    // once the target window is gone
+    pub fn update(&mut self, overlay_window: &Window) -> bool {
+        if !unsafe { IsWindow(self.target) }.as_bool() {
+            return false;
+        }
+
+        let mut rect = RECT::default();
+        if unsafe { GetWindowRect(self.target, &mut rect) }.is_err() {
+            return true;
+        }
+
+        let unchanged = rect.left == self.last_rect.left
+            && rect.top == self.last_rect.top
+            && rect.right == self.last_rect.right
+            && rect.bottom == self.last_rect.bottom;
+        if !self.force_update && unchanged {
+            return true;
+        }
+        self.force_update = false;
+        self.last_rect = rect;
+
+        unsafe {
+            SetWindowPos(
+                HWND(overlay_window.hwnd() as isize),
+                HWND(0),
+                rect.left,
+                rect.top,
+                rect.right - rect.left,
+                rect.bottom - rect.top,
+                SWP_NOACTIVATE | SWP_NOZORDER,
+            );
+        }
+
+        true
+    }
+
+    pub fn mark_force_update(&mut self) {
+        self.force_update = true;
+    }
+
+    // scale factor (96 DPI == 1.0) of the monitor the target currently occupies
+    pub fn target_scale_factor(&self) -> f64 {
+        let dpi = unsafe { GetDpiForWindow(self.target) };
+        if dpi == 0 {
+            1.0
+        } else {
+            dpi as f64 / 96.0
+        }
+    }
+}
+
+pub struct ActiveTracker {
+    active: bool,
+}
+
+impl ActiveTracker {
+    pub fn new() -> Self {
+        Self { active: false }
+    }
+
+    pub fn update(&mut self, window: &Window, _io: &imgui::Io) {
+        self.active = window.is_visible().unwrap_or(true);
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}